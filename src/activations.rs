@@ -0,0 +1,82 @@
+//! Common activation functions and the `ActivationFunction` container.
+
+use std::marker::PhantomData;
+
+use num::Float;
+
+/// A discriminant identifying one of the activation functions defined in
+/// this module, independent of `F`.
+///
+/// Activations are stored as closures inside [`ActivationFunction`] so that
+/// they can be evaluated without a match on every call, but closures cannot
+/// be serialized; `ActivationKind` is the tag used to reconstruct them (see
+/// the `serde` feature on [`crate::feedforward::FeedforwardLayer`]).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub enum ActivationKind {
+    Identity,
+    Step,
+    Sigmoid,
+    Relu,
+}
+
+impl ActivationKind {
+    /// Rebuilds the `ActivationFunction` this kind was tagged from.
+    pub fn build<F: Float>(self) -> ActivationFunction<F, fn(F) -> F, fn(F) -> F> {
+        match self {
+            ActivationKind::Identity => identity(),
+            ActivationKind::Step => step(),
+            ActivationKind::Sigmoid => sigmoid(),
+            ActivationKind::Relu => relu(),
+        }
+    }
+}
+
+/// An activation function paired with its derivative.
+///
+/// Both closures operate on the pre-activation value of a single neuron.
+pub struct ActivationFunction<F: Float, V: Fn(F) -> F, D: Fn(F) -> F> {
+    pub value: V,
+    pub derivative: D,
+    pub kind: ActivationKind,
+    marker: PhantomData<F>,
+}
+
+/// The identity activation `f(x) = x`, mostly useful for linear layers.
+pub fn identity<F: Float>() -> ActivationFunction<F, fn(F) -> F, fn(F) -> F> {
+    fn value<F: Float>(x: F) -> F { x }
+    fn derivative<F: Float>(_: F) -> F { F::one() }
+    ActivationFunction { value: value, derivative: derivative, kind: ActivationKind::Identity, marker: PhantomData }
+}
+
+/// The Heaviside step function, as used by the classic perceptron.
+pub fn step<F: Float>() -> ActivationFunction<F, fn(F) -> F, fn(F) -> F> {
+    fn value<F: Float>(x: F) -> F {
+        if x > F::zero() { F::one() } else { F::zero() }
+    }
+    fn derivative<F: Float>(_: F) -> F { F::one() }
+    ActivationFunction { value: value, derivative: derivative, kind: ActivationKind::Step, marker: PhantomData }
+}
+
+/// The logistic sigmoid `1 / (1 + exp(-x))`.
+pub fn sigmoid<F: Float>() -> ActivationFunction<F, fn(F) -> F, fn(F) -> F> {
+    fn value<F: Float>(x: F) -> F {
+        F::one() / (F::one() + (-x).exp())
+    }
+    fn derivative<F: Float>(x: F) -> F {
+        let s = value(x);
+        s * (F::one() - s)
+    }
+    ActivationFunction { value: value, derivative: derivative, kind: ActivationKind::Sigmoid, marker: PhantomData }
+}
+
+/// The rectified linear unit `f(x) = max(0, x)`.
+pub fn relu<F: Float>() -> ActivationFunction<F, fn(F) -> F, fn(F) -> F> {
+    fn value<F: Float>(x: F) -> F {
+        if x > F::zero() { x } else { F::zero() }
+    }
+    fn derivative<F: Float>(x: F) -> F {
+        if x > F::zero() { F::one() } else { F::zero() }
+    }
+    ActivationFunction { value: value, derivative: derivative, kind: ActivationKind::Relu, marker: PhantomData }
+}
@@ -0,0 +1,58 @@
+//! Loss functions driving backpropagation.
+//!
+//! A [`Cost`] compares a unit's output against a target and produces the
+//! error signal `∂L/∂Y` that seeds [`crate::BackpropTrain::backprop_train`]
+//! at the top of a network.
+
+use num::Float;
+
+/// A loss function comparing an output vector against a target vector.
+pub trait Cost<F> {
+    /// The scalar loss for this `(output, target)` pair.
+    fn eval(&self, output: &[F], target: &[F]) -> F;
+
+    /// The gradient of the loss with respect to `output`, `∂L/∂Y`.
+    fn grad(&self, output: &[F], target: &[F]) -> Vec<F>;
+}
+
+/// The mean squared error, `1/n * sum((y_j - t_j)^2)`.
+///
+/// `grad` returns `y_j - t_j`, i.e. the gradient of `eval` without its
+/// `2/n` factor; the direction is the same, only the step scale differs.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MeanSquared;
+
+impl<F: Float> Cost<F> for MeanSquared {
+    fn eval(&self, output: &[F], target: &[F]) -> F {
+        let n = F::from(output.len()).unwrap();
+        output.iter().zip(target.iter())
+            .map(|(&y, &t)| (y - t) * (y - t))
+            .fold(F::zero(), |acc, v| acc + v) / n
+    }
+
+    fn grad(&self, output: &[F], target: &[F]) -> Vec<F> {
+        output.iter().zip(target.iter()).map(|(&y, &t)| y - t).collect()
+    }
+}
+
+/// The cross-entropy loss, `-sum(t_j * ln(y_j))`.
+///
+/// Pairs naturally with a softmax output layer: `SoftmaxLayer` feeds this
+/// `grad` through the softmax Jacobian, and the combined
+/// softmax+cross-entropy gradient simplifies algebraically to `y_j - t_j`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CrossEntropy;
+
+impl<F: Float> Cost<F> for CrossEntropy {
+    fn eval(&self, output: &[F], target: &[F]) -> F {
+        output.iter().zip(target.iter())
+            .map(|(&y, &t)| -t * y.max(F::min_positive_value()).ln())
+            .fold(F::zero(), |acc, v| acc + v)
+    }
+
+    fn grad(&self, output: &[F], target: &[F]) -> Vec<F> {
+        output.iter().zip(target.iter())
+            .map(|(&y, &t)| -t / y.max(F::min_positive_value()))
+            .collect()
+    }
+}
@@ -0,0 +1,183 @@
+//! Inverted dropout, for regularizing a network during training.
+
+use std::cell::RefCell;
+
+use num::Float;
+
+use {BackpropTrain, Compute, MiniBatchTrain, Mode, SupervisedTrain};
+use cost::Cost;
+use training::WithCost;
+
+/// Zeroes each input with probability `p` and scales the survivors by
+/// `1/(1-p)` (inverted dropout) while in training mode, so that the expected
+/// output magnitude matches inference mode, where inputs pass through
+/// unchanged.
+///
+/// The mask drawn on each [`Compute::compute`] call is cached in `mask` (via
+/// `RefCell`, since `compute` only takes `&self`) so that the following
+/// `backprop_train` call multiplies the incoming error by the very same
+/// mask, rather than an independently-resampled one.
+pub struct Dropout<F: Float, G: FnMut() -> F> {
+    size: usize,
+    p: F,
+    train: bool,
+    generator: RefCell<G>,
+    mask: RefCell<Vec<F>>,
+}
+
+impl<F: Float, G: FnMut() -> F> Dropout<F, G> {
+    /// Creates a dropout layer over vectors of length `size`, dropping each
+    /// component with probability `p`. `generator` is expected to yield
+    /// uniform values in `[0, 1)`, drawn once per component on every
+    /// training-mode `compute` call.
+    pub fn new(size: usize, p: F, generator: G) -> Dropout<F, G> {
+        Dropout {
+            size: size,
+            p: p,
+            train: true,
+            generator: RefCell::new(generator),
+            mask: RefCell::new(vec![F::zero(); size]),
+        }
+    }
+}
+
+impl<F: Float, G: FnMut() -> F> Compute<F> for Dropout<F, G> {
+    fn compute(&self, input: &[F]) -> Vec<F> {
+        if !self.train {
+            return input.to_owned();
+        }
+
+        let scale = F::one() / (F::one() - self.p);
+        let mut generator = self.generator.borrow_mut();
+        let mask: Vec<F> = input.iter()
+            .map(|_| if generator() < self.p { F::zero() } else { scale })
+            .collect();
+
+        let output = input.iter().zip(mask.iter()).map(|(&x, &m)| x * m).collect();
+        *self.mask.borrow_mut() = mask;
+        output
+    }
+
+    fn input_size(&self) -> usize {
+        self.size
+    }
+
+    fn output_size(&self) -> usize {
+        self.size
+    }
+}
+
+impl<F: Float, G: FnMut() -> F> Mode for Dropout<F, G> {
+    fn set_train(&mut self, train: bool) {
+        self.train = train;
+    }
+}
+
+impl<F: Float, G: FnMut() -> F, R> BackpropTrain<F, R> for Dropout<F, G> {
+    /// Dropout has no parameters of its own; this just multiplies `error`
+    /// by the mask drawn on the last `compute` call (identity in inference
+    /// mode) and returns the result as `∂L/∂X`.
+    fn backprop_train(&mut self, _rule: &R, _input: &[F], error: &[F]) -> Vec<F> {
+        if !self.train {
+            return error.to_owned();
+        }
+        let mask = self.mask.borrow();
+        error.iter().zip(mask.iter()).map(|(&e, &m)| e * m).collect()
+    }
+}
+
+impl<F: Float, G: FnMut() -> F, R: WithCost<F>> SupervisedTrain<F, R> for Dropout<F, G>
+    where Dropout<F, G>: BackpropTrain<F, R>
+{
+    fn supervised_train(&mut self, rule: &R, input: &[F], target: &[F]) {
+        let output = self.compute(input);
+        let error = rule.cost().grad(&output, target);
+        self.backprop_train(rule, input, &error);
+    }
+}
+
+impl<F: Float, G: FnMut() -> F, R> MiniBatchTrain<F, R> for Dropout<F, G> {
+    /// Dropout has no parameters, so there's nothing to accumulate.
+    type Batch = ();
+
+    fn new_batch(&self) -> () {}
+
+    fn accumulate(&self, _rule: &R, _input: &[F], error: &[F], _batch: &mut ()) -> Vec<F> {
+        if !self.train {
+            return error.to_owned();
+        }
+        let mask = self.mask.borrow();
+        error.iter().zip(mask.iter()).map(|(&e, &m)| e * m).collect()
+    }
+
+    fn apply(&mut self, _rule: &R, _batch: (), _count: usize) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use Compute;
+    use Mode;
+    use SupervisedTrain;
+
+    use activations::sigmoid;
+    use feedforward::FeedforwardLayer;
+    use training::GradientDescent;
+    use util::Chain;
+
+    use super::Dropout;
+
+    #[test]
+    fn eval_mode_passes_through() {
+        let mut layer = Dropout::new(4, 0.5f32, || 0.0f32);
+        layer.set_train(false);
+        assert_eq!(layer.compute(&[1.0, 2.0, 3.0, 4.0]), [1.0f32, 2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn train_mode_masks_and_scales() {
+        // alternately below and above p=0.5, so components are dropped and
+        // kept in a known pattern.
+        let mut toggle = false;
+        let layer = Dropout::new(4, 0.5f32, move || { toggle = !toggle; if toggle { 0.1 } else { 0.9 } });
+        let output = layer.compute(&[1.0f32, 1.0, 1.0, 1.0]);
+        assert_eq!(output, [0.0f32, 2.0, 0.0, 2.0]);
+    }
+
+    #[test]
+    fn backprop_train_reuses_the_forward_mask() {
+        use BackpropTrain;
+
+        let mut toggle = false;
+        let mut layer = Dropout::new(4, 0.5f32, move || { toggle = !toggle; if toggle { 0.1 } else { 0.9 } });
+        layer.compute(&[1.0f32, 1.0, 1.0, 1.0]);
+
+        let propagated = layer.backprop_train(&(), &[0.0, 0.0, 0.0, 0.0], &[1.0f32, 1.0, 1.0, 1.0]);
+        assert_eq!(propagated, [0.0f32, 2.0, 0.0, 2.0]);
+    }
+
+    #[test]
+    fn chains_with_a_feedforward_layer_and_toggles_mode() {
+        // the affine -> dropout pattern this layer was added for, composed
+        // through `Chain` and trained end to end.
+        let mut net = Chain::new(
+            FeedforwardLayer::new_from(4, 4, sigmoid(), {
+                let mut acc = 0;
+                move || { acc += 1; (1.0f32 + ((13*acc) % 12) as f32) / 13.0f32 }
+            }),
+            Dropout::new(4, 0.5f32, {
+                let mut acc = 0;
+                move || { acc += 1; (1.0f32 + ((7*acc) % 10) as f32) / 10.0f32 }
+            }),
+        );
+
+        let rule = GradientDescent::new(0.1f32);
+        net.supervised_train(&rule, &[1.0, 1.0, 1.0, 1.0], &[0.5, 0.5, 0.5, 0.5]);
+
+        // toggling eval mode through the whole chain makes it deterministic
+        // and pass-through for the dropout half.
+        net.set_train(false);
+        let a = net.compute(&[1.0, 1.0, 1.0, 1.0]);
+        let b = net.compute(&[1.0, 1.0, 1.0, 1.0]);
+        assert_eq!(a, b);
+    }
+}
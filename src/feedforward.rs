@@ -4,9 +4,10 @@ use std::cmp::min;
 
 use num::{Float, zero};
 
-use {Compute, BackpropTrain, SupervisedTrain};
+use {Compute, BackpropTrain, MiniBatchTrain, Mode, SupervisedTrain};
 use activations::ActivationFunction;
-use training::{PerceptronRule, GradientDescent};
+use cost::Cost;
+use training::{Adam, AdamState, GradientDescent, Momentum, MomentumState, PerceptronRule, WithCost};
 
 /// A feedforward layer
 ///
@@ -27,7 +28,12 @@ pub struct FeedforwardLayer<F: Float, V: Fn(F) -> F, D: Fn(F) -> F> {
     inputs: usize,
     coeffs: Vec<F>,
     biases: Vec<F>,
-    activation: ActivationFunction<F, V, D>
+    activation: ActivationFunction<F, V, D>,
+    // Lazily initialized on first use by the matching optimizer, since most
+    // layers are never trained with momentum/ADAM and don't need the extra
+    // per-parameter accumulators.
+    momentum_state: Option<MomentumState<F>>,
+    adam_state: Option<AdamState<F>>,
 }
 
 impl<F, V, D> FeedforwardLayer<F, V, D>
@@ -46,7 +52,9 @@ impl<F, V, D> FeedforwardLayer<F, V, D>
             inputs: inputs,
             coeffs: vec![zero(); inputs*outputs],
             biases: vec![zero(); outputs],
-            activation: activation
+            activation: activation,
+            momentum_state: None,
+            adam_state: None,
         }
     }
 
@@ -63,7 +71,9 @@ impl<F, V, D> FeedforwardLayer<F, V, D>
             inputs: inputs,
             coeffs: (0..inputs*outputs).map(|_| generator()).collect(),
             biases: (0..outputs).map(|_| generator()).collect(),
-            activation: activation
+            activation: activation,
+            momentum_state: None,
+            adam_state: None,
         }
     }
 
@@ -81,7 +91,9 @@ impl<F, V, D> FeedforwardLayer<F, V, D>
             inputs: inputs,
             coeffs: (0..inputs*outputs).map(|_| weight_generator()).collect(),
             biases: (0..outputs).map(|_| bias_generator()).collect(),
-            activation: activation
+            activation: activation,
+            momentum_state: None,
+            adam_state: None,
         }
     }
 
@@ -97,7 +109,9 @@ impl<F, V, D> FeedforwardLayer<F, V, D>
             inputs: inputs,
             coeffs: coefficients,
             biases: biases,
-            activation: activation
+            activation: activation,
+            momentum_state: None,
+            adam_state: None,
         }
     }
 
@@ -125,7 +139,136 @@ impl<F, V, D> FeedforwardLayer<F, V, D>
         // some values should be re-initialized
         self.biases = biases;
     }
- 
+
+    /// Computes this layer's output for a whole batch of inputs at once,
+    /// numerically equivalent to calling [`Compute::compute`] on each input
+    /// separately.
+    ///
+    /// Unlike `compute`'s single `j`-outer/`i`-inner loop, this walks
+    /// `coeffs` in `BATCH_TILE`-sized blocks of inputs and outputs, so each
+    /// loaded tile of weights is reused across every vector in the batch
+    /// before moving on — friendlier to the cache and easier for the
+    /// compiler to auto-vectorize than the naive scalar path, which matters
+    /// once `inputs*outputs` gets large.
+    pub fn compute_batch(&self, inputs: &[&[F]]) -> Vec<Vec<F>> {
+        const BATCH_TILE: usize = 8;
+
+        let outputs = self.biases.len();
+        let mut results: Vec<Vec<F>> = inputs.iter().map(|_| self.biases.clone()).collect();
+
+        let mut j0 = 0;
+        while j0 < outputs {
+            let j1 = min(j0 + BATCH_TILE, outputs);
+            let mut i0 = 0;
+            while i0 < self.inputs {
+                let i1 = min(i0 + BATCH_TILE, self.inputs);
+                for (result, input) in results.iter_mut().zip(inputs.iter()) {
+                    let len = min(i1, input.len());
+                    for j in j0..j1 {
+                        // accumulate straight into `result[j]`, in the same
+                        // order as `compute`, so results match it exactly.
+                        for i in i0..len {
+                            result[j] = result[j] + self.coeffs[j*self.inputs + i] * input[i];
+                        }
+                    }
+                }
+                i0 += BATCH_TILE;
+            }
+            j0 += BATCH_TILE;
+        }
+
+        for result in &mut results {
+            for o in result.iter_mut() {
+                *o = (self.activation.value)(*o);
+            }
+        }
+        results
+    }
+
+}
+
+/// On-disk model for a [`FeedforwardLayer`], saved/loaded as JSON.
+///
+/// Activations are closures and can't be serialized directly, so the layer
+/// is tagged by its [`ActivationKind`] and rebuilt via
+/// [`ActivationKind::build`] on load; this is why (de)serialization is only
+/// implemented for layers built from the named activation functions, i.e.
+/// `FeedforwardLayer<F, fn(F) -> F, fn(F) -> F>`.
+#[cfg(feature = "serde")]
+#[derive(::serde::Serialize, ::serde::Deserialize)]
+struct LayerModel<F> {
+    inputs: usize,
+    coeffs: Vec<F>,
+    biases: Vec<F>,
+    activation: ::activations::ActivationKind,
+}
+
+#[cfg(feature = "serde")]
+impl<F> ::serde::Serialize for FeedforwardLayer<F, fn(F) -> F, fn(F) -> F>
+    where F: Float + ::serde::Serialize
+{
+    /// Delegates to [`LayerModel`], dropping the lazily-initialized
+    /// optimizer state, which is rebuilt on first use anyway.
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: ::serde::Serializer
+    {
+        LayerModel {
+            inputs: self.inputs,
+            coeffs: self.coeffs.clone(),
+            biases: self.biases.clone(),
+            activation: self.activation.kind,
+        }.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, F> ::serde::Deserialize<'de> for FeedforwardLayer<F, fn(F) -> F, fn(F) -> F>
+    where F: Float + ::serde::Deserialize<'de>
+{
+    /// Rebuilds a [`LayerModel`]'s activation from its saved
+    /// [`ActivationKind`].
+    fn deserialize<De>(deserializer: De) -> Result<Self, De::Error>
+        where De: ::serde::Deserializer<'de>
+    {
+        let model = LayerModel::<F>::deserialize(deserializer)?;
+        Ok(FeedforwardLayer::new_from_values(
+            model.inputs,
+            model.biases.len(),
+            model.activation.build(),
+            model.coeffs,
+            model.biases,
+        ))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<F> FeedforwardLayer<F, fn(F) -> F, fn(F) -> F>
+    where F: Float + ::serde::Serialize + ::serde::de::DeserializeOwned
+{
+    /// Saves this layer's weights, biases and activation kind as JSON.
+    pub fn save_to<P: AsRef<::std::path::Path>>(&self, path: P) -> ::std::io::Result<()> {
+        let file = ::std::fs::File::create(path)?;
+        ::serde_json::to_writer(file, self).map_err(|e| ::std::io::Error::new(::std::io::ErrorKind::Other, e))
+    }
+
+    /// Loads a layer previously written by [`FeedforwardLayer::save_to`],
+    /// rebuilding its activation from the saved [`ActivationKind`].
+    pub fn load_from<P: AsRef<::std::path::Path>>(path: P) -> ::std::io::Result<FeedforwardLayer<F, fn(F) -> F, fn(F) -> F>> {
+        let file = ::std::fs::File::open(path)?;
+        ::serde_json::from_reader(file).map_err(|e| ::std::io::Error::new(::std::io::ErrorKind::Other, e))
+    }
+}
+
+impl<F, V, D> Mode for FeedforwardLayer<F, V, D>
+    where F: Float,
+          V: Fn(F) -> F,
+          D: Fn(F) -> F
+{
+    /// A no-op: this layer's computation doesn't depend on training/eval
+    /// mode, but the impl lets it sit anywhere in a `Chain` alongside units
+    /// that do (e.g. `dropout::Dropout`) without breaking `Chain`'s own
+    /// `Mode` impl.
+    fn set_train(&mut self, _train: bool) {}
 }
 
 impl<F, V, D> Compute<F> for FeedforwardLayer<F, V, D>
@@ -179,71 +322,393 @@ impl<F, V, D> SupervisedTrain<F, PerceptronRule<F>> for FeedforwardLayer<F, V, D
     }
 }
 
-impl<F, V, D> BackpropTrain<F, GradientDescent<F>> for FeedforwardLayer<F, V, D>
+impl<F, V, D> FeedforwardLayer<F, V, D>
+    where F: Float,
+          V: Fn(F) -> F,
+          D: Fn(F) -> F
+{
+    /// Computes each neuron's `delta[j] = error[j] * f'(preactivation[j])`
+    /// and the error signal `∂L/∂X` to propagate to the previous layer,
+    /// shared by every `BackpropTrain` impl regardless of optimizer.
+    fn deltas_and_propagated(&self, input: &[F], error: &[F]) -> (Vec<F>, Vec<F>) {
+        let mut preactivation = self.biases.clone();
+        for j in 0..self.biases.len() {
+            for i in 0..min(self.inputs, input.len()) {
+                preactivation[j] = preactivation[j] + self.coeffs[j*self.inputs + i] * input[i]
+            }
+        }
+
+        let deltas = preactivation.iter().enumerate()
+                            .map(|(j, x)| {
+                                error.get(j).map(|e| *e).unwrap_or(zero())
+                                    * (self.activation.derivative)(*x)
+                            })
+                            .collect::<Vec<_>>();
+
+        let mut propagated = vec![zero(); self.inputs];
+        for j in 0..self.biases.len() {
+            for i in 0..min(self.inputs, input.len()) {
+                propagated[i] = propagated[i] + self.coeffs[i + j*self.inputs] * deltas[j];
+            }
+        }
+
+        (deltas, propagated)
+    }
+}
+
+impl<F, V, D, C> BackpropTrain<F, GradientDescent<F, C>> for FeedforwardLayer<F, V, D>
     where F: Float,
           V: Fn(F) -> F,
           D: Fn(F) -> F
 {
+    /// Updates this layer's weights and biases given the incoming error
+    /// signal `error` (`∂L/∂Y`), and returns `∂L/∂X` for the previous layer.
     fn backprop_train(&mut self,
-                      rule: &GradientDescent<F>,
+                      rule: &GradientDescent<F, C>,
                       input: &[F],
-                      target: &[F])
+                      error: &[F])
         -> Vec<F>
     {
-        // we need to compute the intermediate states
-        let mut out = self.biases.clone();
+        let (deltas, propagated) = self.deltas_and_propagated(input, error);
+
         for j in 0..self.biases.len() {
             for i in 0..min(self.inputs, input.len()) {
-                out[j] = out[j] + self.coeffs[j*self.inputs + i] * input[i]
+                let idx = i + j*self.inputs;
+                let mut update = rule.rate * input[i] * deltas[j];
+                if let Some(ref regularization) = rule.regularization {
+                    update = update + regularization.weight_decay(rule.rate, self.coeffs[idx]);
+                }
+                self.coeffs[idx] = self.coeffs[idx] - update;
             }
+            self.biases[j] = self.biases[j] - rule.rate * deltas[j];
         }
+        propagated
+    }
+}
 
-        let deltas = out.iter()
-                            .map(|x| { (self.activation.derivative)(*x) })
-                            .collect::<Vec<_>>();
-        for o in &mut out {
-            *o = (self.activation.value)(*o);
+impl<F, V, D, C> BackpropTrain<F, Momentum<F, C>> for FeedforwardLayer<F, V, D>
+    where F: Float,
+          V: Fn(F) -> F,
+          D: Fn(F) -> F
+{
+    /// Like the plain gradient descent update, but accumulates a
+    /// per-parameter velocity (`v = mu*v - rate*grad; w += v`) in the
+    /// layer's momentum state instead of applying the raw gradient.
+    fn backprop_train(&mut self,
+                      rule: &Momentum<F, C>,
+                      input: &[F],
+                      error: &[F])
+        -> Vec<F>
+    {
+        let (deltas, propagated) = self.deltas_and_propagated(input, error);
+
+        let coeffs_len = self.coeffs.len();
+        let biases_len = self.biases.len();
+        let state = self.momentum_state
+            .get_or_insert_with(|| MomentumState::zeros(coeffs_len, biases_len));
+
+        for j in 0..self.biases.len() {
+            for i in 0..min(self.inputs, input.len()) {
+                let idx = i + j*self.inputs;
+                let grad = input[i] * deltas[j];
+                state.coeff_velocity[idx] = rule.mu * state.coeff_velocity[idx] - rule.rate * grad;
+                self.coeffs[idx] = self.coeffs[idx] + state.coeff_velocity[idx];
+            }
+            state.bias_velocity[j] = rule.mu * state.bias_velocity[j] - rule.rate * deltas[j];
+            self.biases[j] = self.biases[j] + state.bias_velocity[j];
         }
+        propagated
+    }
+}
+
+impl<F, V, D, C> BackpropTrain<F, Adam<F, C>> for FeedforwardLayer<F, V, D>
+    where F: Float,
+          V: Fn(F) -> F,
+          D: Fn(F) -> F
+{
+    /// Like the plain gradient descent update, but rescales each parameter's
+    /// step by its bias-corrected first/second moment estimates, per the
+    /// ADAM update rule.
+    fn backprop_train(&mut self,
+                      rule: &Adam<F, C>,
+                      input: &[F],
+                      error: &[F])
+        -> Vec<F>
+    {
+        let (deltas, propagated) = self.deltas_and_propagated(input, error);
+
+        let coeffs_len = self.coeffs.len();
+        let biases_len = self.biases.len();
+        let state = self.adam_state
+            .get_or_insert_with(|| AdamState::zeros(coeffs_len, biases_len));
+        state.t += 1;
+
+        let one = F::one();
+        let b1_correction = one - rule.b1.powi(state.t);
+        let b2_correction = one - rule.b2.powi(state.t);
 
-        let mut returned = input.to_owned();
         for j in 0..self.biases.len() {
             for i in 0..min(self.inputs, input.len()) {
-                returned[i] = returned[i] - self.coeffs[i + j*self.inputs]*deltas[j];
-                self.coeffs[i + j*self.inputs] =
-                    self.coeffs[i + j*self.inputs]
-                    - rule.rate * input.get(i).map(|x| *x).unwrap_or(zero())
-                                * deltas[j]
-                                * ( out[j] - target.get(j).map(|x| *x).unwrap_or(zero()) )
+                let idx = i + j*self.inputs;
+                let grad = input[i] * deltas[j];
+                state.coeff_m[idx] = rule.b1 * state.coeff_m[idx] + (one - rule.b1) * grad;
+                state.coeff_s[idx] = rule.b2 * state.coeff_s[idx] + (one - rule.b2) * grad * grad;
+                let m_hat = state.coeff_m[idx] / b1_correction;
+                let s_hat = state.coeff_s[idx] / b2_correction;
+                self.coeffs[idx] = self.coeffs[idx] - rule.rate * m_hat / (s_hat.sqrt() + rule.eps);
+            }
+            state.bias_m[j] = rule.b1 * state.bias_m[j] + (one - rule.b1) * deltas[j];
+            state.bias_s[j] = rule.b2 * state.bias_s[j] + (one - rule.b2) * deltas[j] * deltas[j];
+            let m_hat = state.bias_m[j] / b1_correction;
+            let s_hat = state.bias_s[j] / b2_correction;
+            self.biases[j] = self.biases[j] - rule.rate * m_hat / (s_hat.sqrt() + rule.eps);
+        }
+        propagated
+    }
+}
+
+/// Scratch buffers for [`FeedforwardLayer`]'s [`MiniBatchTrain`] impl, shaped
+/// like its `coeffs`/`biases`.
+pub struct FeedforwardBatch<F> {
+    coeff_grad: Vec<F>,
+    bias_grad: Vec<F>,
+}
+
+impl<F, V, D, C> MiniBatchTrain<F, GradientDescent<F, C>> for FeedforwardLayer<F, V, D>
+    where F: Float,
+          V: Fn(F) -> F,
+          D: Fn(F) -> F
+{
+    type Batch = FeedforwardBatch<F>;
+
+    fn new_batch(&self) -> FeedforwardBatch<F> {
+        FeedforwardBatch {
+            coeff_grad: vec![zero(); self.coeffs.len()],
+            bias_grad: vec![zero(); self.biases.len()],
+        }
+    }
+
+    fn accumulate(&self,
+                 rule: &GradientDescent<F, C>,
+                 input: &[F],
+                 error: &[F],
+                 batch: &mut FeedforwardBatch<F>)
+        -> Vec<F>
+    {
+        let (deltas, propagated) = self.deltas_and_propagated(input, error);
 
+        for j in 0..self.biases.len() {
+            for i in 0..min(self.inputs, input.len()) {
+                let idx = i + j*self.inputs;
+                let mut grad = input[i] * deltas[j];
+                if let Some(ref regularization) = rule.regularization {
+                    // `rate` is deferred to `apply`, which multiplies the
+                    // whole averaged gradient (this penalty term included)
+                    // by `rule.rate` in one place — passing `rule.rate` here
+                    // too would double-apply it to the penalty.
+                    grad = grad + regularization.weight_decay(F::one(), self.coeffs[idx]);
+                }
+                batch.coeff_grad[idx] = batch.coeff_grad[idx] + grad;
             }
-            self.biases[j] = self.biases[j]
-                    - rule.rate * deltas[j]
-                                * ( out[j] - target.get(j).map(|x| *x).unwrap_or(zero()) );
+            batch.bias_grad[j] = batch.bias_grad[j] + deltas[j];
         }
-        returned
+        propagated
+    }
+
+    fn apply(&mut self, rule: &GradientDescent<F, C>, batch: FeedforwardBatch<F>, count: usize) {
+        let n = F::from(count).unwrap();
+        for (idx, grad) in batch.coeff_grad.into_iter().enumerate() {
+            self.coeffs[idx] = self.coeffs[idx] - rule.rate * grad / n;
+        }
+        for (j, grad) in batch.bias_grad.into_iter().enumerate() {
+            self.biases[j] = self.biases[j] - rule.rate * grad / n;
+        }
+    }
+
+    fn regularization_penalty(&self, rule: &GradientDescent<F, C>) -> F {
+        rule.regularization_penalty(&self.coeffs)
     }
 }
 
-impl<F, V, D> SupervisedTrain<F, GradientDescent<F>> for FeedforwardLayer<F, V, D>
+impl<F, V, D, C> MiniBatchTrain<F, Momentum<F, C>> for FeedforwardLayer<F, V, D>
     where F: Float,
           V: Fn(F) -> F,
           D: Fn(F) -> F
 {
-    fn supervised_train(&mut self,
-                        rule: &GradientDescent<F>,
-                        input: &[F],
-                        target: &[F])
+    type Batch = FeedforwardBatch<F>;
+
+    fn new_batch(&self) -> FeedforwardBatch<F> {
+        FeedforwardBatch {
+            coeff_grad: vec![zero(); self.coeffs.len()],
+            bias_grad: vec![zero(); self.biases.len()],
+        }
+    }
+
+    fn accumulate(&self,
+                 _rule: &Momentum<F, C>,
+                 input: &[F],
+                 error: &[F],
+                 batch: &mut FeedforwardBatch<F>)
+        -> Vec<F>
+    {
+        let (deltas, propagated) = self.deltas_and_propagated(input, error);
+
+        for j in 0..self.biases.len() {
+            for i in 0..min(self.inputs, input.len()) {
+                let idx = i + j*self.inputs;
+                batch.coeff_grad[idx] = batch.coeff_grad[idx] + input[i] * deltas[j];
+            }
+            batch.bias_grad[j] = batch.bias_grad[j] + deltas[j];
+        }
+        propagated
+    }
+
+    /// Takes one momentum step per batch, using the batch's mean gradient in
+    /// place of a single example's, same as [`BackpropTrain::backprop_train`]'s
+    /// `v = mu*v - rate*grad; w += v` update.
+    fn apply(&mut self, rule: &Momentum<F, C>, batch: FeedforwardBatch<F>, count: usize) {
+        let n = F::from(count).unwrap();
+        let coeffs_len = self.coeffs.len();
+        let biases_len = self.biases.len();
+        let state = self.momentum_state
+            .get_or_insert_with(|| MomentumState::zeros(coeffs_len, biases_len));
+
+        for (idx, grad) in batch.coeff_grad.into_iter().enumerate() {
+            let grad = grad / n;
+            state.coeff_velocity[idx] = rule.mu * state.coeff_velocity[idx] - rule.rate * grad;
+            self.coeffs[idx] = self.coeffs[idx] + state.coeff_velocity[idx];
+        }
+        for (j, grad) in batch.bias_grad.into_iter().enumerate() {
+            let grad = grad / n;
+            state.bias_velocity[j] = rule.mu * state.bias_velocity[j] - rule.rate * grad;
+            self.biases[j] = self.biases[j] + state.bias_velocity[j];
+        }
+    }
+}
+
+impl<F, V, D, C> MiniBatchTrain<F, Adam<F, C>> for FeedforwardLayer<F, V, D>
+    where F: Float,
+          V: Fn(F) -> F,
+          D: Fn(F) -> F
+{
+    type Batch = FeedforwardBatch<F>;
+
+    fn new_batch(&self) -> FeedforwardBatch<F> {
+        FeedforwardBatch {
+            coeff_grad: vec![zero(); self.coeffs.len()],
+            bias_grad: vec![zero(); self.biases.len()],
+        }
+    }
+
+    fn accumulate(&self,
+                 _rule: &Adam<F, C>,
+                 input: &[F],
+                 error: &[F],
+                 batch: &mut FeedforwardBatch<F>)
+        -> Vec<F>
+    {
+        let (deltas, propagated) = self.deltas_and_propagated(input, error);
+
+        for j in 0..self.biases.len() {
+            for i in 0..min(self.inputs, input.len()) {
+                let idx = i + j*self.inputs;
+                batch.coeff_grad[idx] = batch.coeff_grad[idx] + input[i] * deltas[j];
+            }
+            batch.bias_grad[j] = batch.bias_grad[j] + deltas[j];
+        }
+        propagated
+    }
+
+    /// Takes one ADAM step per batch (bumping `t` once), using the batch's
+    /// mean gradient in place of a single example's.
+    fn apply(&mut self, rule: &Adam<F, C>, batch: FeedforwardBatch<F>, count: usize) {
+        let n = F::from(count).unwrap();
+        let coeffs_len = self.coeffs.len();
+        let biases_len = self.biases.len();
+        let state = self.adam_state
+            .get_or_insert_with(|| AdamState::zeros(coeffs_len, biases_len));
+        state.t += 1;
+
+        let one = F::one();
+        let b1_correction = one - rule.b1.powi(state.t);
+        let b2_correction = one - rule.b2.powi(state.t);
+
+        for (idx, grad) in batch.coeff_grad.into_iter().enumerate() {
+            let grad = grad / n;
+            state.coeff_m[idx] = rule.b1 * state.coeff_m[idx] + (one - rule.b1) * grad;
+            state.coeff_s[idx] = rule.b2 * state.coeff_s[idx] + (one - rule.b2) * grad * grad;
+            let m_hat = state.coeff_m[idx] / b1_correction;
+            let s_hat = state.coeff_s[idx] / b2_correction;
+            self.coeffs[idx] = self.coeffs[idx] - rule.rate * m_hat / (s_hat.sqrt() + rule.eps);
+        }
+        for (j, grad) in batch.bias_grad.into_iter().enumerate() {
+            let grad = grad / n;
+            state.bias_m[j] = rule.b1 * state.bias_m[j] + (one - rule.b1) * grad;
+            state.bias_s[j] = rule.b2 * state.bias_s[j] + (one - rule.b2) * grad * grad;
+            let m_hat = state.bias_m[j] / b1_correction;
+            let s_hat = state.bias_s[j] / b2_correction;
+            self.biases[j] = self.biases[j] - rule.rate * m_hat / (s_hat.sqrt() + rule.eps);
+        }
+    }
+}
+
+impl<F, V, D> FeedforwardLayer<F, V, D>
+    where F: Float,
+          V: Fn(F) -> F,
+          D: Fn(F) -> F
+{
+    /// Shared by every backprop-based `SupervisedTrain` impl: compute the
+    /// initial error signal from `rule`'s `Cost` and thread it through
+    /// `backprop_train`.
+    fn supervised_train_with_cost<R>(&mut self, rule: &R, input: &[F], target: &[F])
+        where R: WithCost<F>,
+              FeedforwardLayer<F, V, D>: BackpropTrain<F, R>
     {
-        self.backprop_train(rule, input, target);
+        let output = self.compute(input);
+        let error = rule.cost().grad(&output, target);
+        self.backprop_train(rule, input, &error);
+    }
+}
+
+impl<F, V, D, C> SupervisedTrain<F, GradientDescent<F, C>> for FeedforwardLayer<F, V, D>
+    where F: Float,
+          V: Fn(F) -> F,
+          D: Fn(F) -> F,
+          C: Cost<F>
+{
+    fn supervised_train(&mut self, rule: &GradientDescent<F, C>, input: &[F], target: &[F]) {
+        self.supervised_train_with_cost(rule, input, target);
+    }
+}
+
+impl<F, V, D, C> SupervisedTrain<F, Momentum<F, C>> for FeedforwardLayer<F, V, D>
+    where F: Float,
+          V: Fn(F) -> F,
+          D: Fn(F) -> F,
+          C: Cost<F>
+{
+    fn supervised_train(&mut self, rule: &Momentum<F, C>, input: &[F], target: &[F]) {
+        self.supervised_train_with_cost(rule, input, target);
+    }
+}
+
+impl<F, V, D, C> SupervisedTrain<F, Adam<F, C>> for FeedforwardLayer<F, V, D>
+    where F: Float,
+          V: Fn(F) -> F,
+          D: Fn(F) -> F,
+          C: Cost<F>
+{
+    fn supervised_train(&mut self, rule: &Adam<F, C>, input: &[F], target: &[F]) {
+        self.supervised_train_with_cost(rule, input, target);
     }
 }
 
 #[cfg(test)]
 mod tests {
 
-    use {Compute, SupervisedTrain};
+    use {Compute, MiniBatchTrain, SupervisedTrain};
     use activations::{identity, step, sigmoid};
-    use training::{PerceptronRule, GradientDescent};
+    use training::{Adam, Momentum, PerceptronRule, GradientDescent, Regularization};
     use util::Chain;
 
     use super::FeedforwardLayer;
@@ -286,7 +751,7 @@ mod tests {
             move || { acc += 1; (1.0f32 + ((13*acc) % 12) as f32) / 13.0f32}
         };
         let mut layer = FeedforwardLayer::new_from(4, 2, sigmoid(), random);
-        let rule = GradientDescent { rate: 0.5f32 };
+        let rule = GradientDescent::new(0.5f32);
         for _ in 0..40 {
             layer.supervised_train(&rule, &[1.0,1.0,1.0,1.0], &[0.0, 0.0]);
             layer.supervised_train(&rule, &[1.0,-1.0,1.0,-1.0], &[1.0, 1.0]);
@@ -304,7 +769,7 @@ mod tests {
             move || { acc += 1; (1.0f32 + ((13*acc) % 12) as f32) / 13.0f32}
         };
         let mut layer = Chain::new(FeedforwardLayer::new_from(4, 8, sigmoid(), &mut random), FeedforwardLayer::new_from(8, 2, sigmoid(), &mut random));
-        let rule = GradientDescent { rate: 0.5f32 };
+        let rule = GradientDescent::new(0.5f32);
         for _ in 0..200 {
             layer.supervised_train(&rule, &[1.0, 1.0,1.0, 1.0], &[1.0, 0.0]);
             layer.supervised_train(&rule, &[1.0,-1.0,1.0,-1.0], &[0.0, 1.0]);
@@ -314,4 +779,108 @@ mod tests {
         println!("{:?}", layer.compute(&[1.0, -1.0, 1.0, -1.0]));
         assert!({ let out = layer.compute(&[1.0, -1.0, 1.0, -1.0]); out[0] < 0.2 && out[1] > 0.8 });
     }
+
+    #[test]
+    fn momentum() {
+        let mut random = {
+            let mut acc = 0;
+            move || { acc += 1; (1.0f32 + ((13*acc) % 12) as f32) / 13.0f32}
+        };
+        let mut layer = Chain::new(FeedforwardLayer::new_from(4, 8, sigmoid(), &mut random), FeedforwardLayer::new_from(8, 2, sigmoid(), &mut random));
+        let rule = Momentum::new(0.3f32, 0.9f32);
+        for _ in 0..200 {
+            layer.supervised_train(&rule, &[1.0, 1.0,1.0, 1.0], &[1.0, 0.0]);
+            layer.supervised_train(&rule, &[1.0,-1.0,1.0,-1.0], &[0.0, 1.0]);
+        }
+        assert!({ let out = layer.compute(&[1.0, 1.0, 1.0, 1.0]); out[0] > 0.8 && out[1] < 0.2 });
+        assert!({ let out = layer.compute(&[1.0, -1.0, 1.0, -1.0]); out[0] < 0.2 && out[1] > 0.8 });
+    }
+
+    #[test]
+    fn adam() {
+        let mut random = {
+            let mut acc = 0;
+            move || { acc += 1; (1.0f32 + ((13*acc) % 12) as f32) / 13.0f32}
+        };
+        let mut layer = Chain::new(FeedforwardLayer::new_from(4, 8, sigmoid(), &mut random), FeedforwardLayer::new_from(8, 2, sigmoid(), &mut random));
+        let rule = Adam::new(0.1f32);
+        for _ in 0..200 {
+            layer.supervised_train(&rule, &[1.0, 1.0,1.0, 1.0], &[1.0, 0.0]);
+            layer.supervised_train(&rule, &[1.0,-1.0,1.0,-1.0], &[0.0, 1.0]);
+        }
+        assert!({ let out = layer.compute(&[1.0, 1.0, 1.0, 1.0]); out[0] > 0.8 && out[1] < 0.2 });
+        assert!({ let out = layer.compute(&[1.0, -1.0, 1.0, -1.0]); out[0] < 0.2 && out[1] > 0.8 });
+    }
+
+    #[test]
+    fn l2_regularization_shrinks_weights() {
+        let mut with_decay = FeedforwardLayer::new_from(4, 2, identity(), || 0.5f32);
+        let mut without_decay = FeedforwardLayer::new_from(4, 2, identity(), || 0.5f32);
+        let decaying_rule = GradientDescent::new(0.1f32).with_regularization(Regularization::L2(0.1f32));
+        let plain_rule = GradientDescent::new(0.1f32);
+
+        // both layers see the same gradient step; only `decaying_rule` adds
+        // the extra `-rate*lambda*w` pulling weights towards zero.
+        with_decay.supervised_train(&decaying_rule, &[1.0, 1.0, 1.0, 1.0], &[0.0, 0.0]);
+        without_decay.supervised_train(&plain_rule, &[1.0, 1.0, 1.0, 1.0], &[0.0, 0.0]);
+
+        for (decayed, plain) in with_decay.get_coefficients().iter().zip(without_decay.get_coefficients()) {
+            assert!(decayed < plain);
+        }
+    }
+
+    #[test]
+    fn batched_l2_regularization_decays_by_the_exact_amount() {
+        // a zero error signal isolates the penalty term: with no gradient to
+        // add, `accumulate` only contributes `weight_decay(1, w)`, so `apply`
+        // should shrink every weight by exactly `rate * lambda * w`.
+        let mut layer = FeedforwardLayer::new_from(4, 2, identity(), || 0.5f32);
+        let rule = GradientDescent::new(0.1f32).with_regularization(Regularization::L2(0.1f32));
+
+        let mut batch = MiniBatchTrain::<f32, GradientDescent<f32>>::new_batch(&layer);
+        layer.accumulate(&rule, &[1.0, 1.0, 1.0, 1.0], &[0.0, 0.0], &mut batch);
+        layer.apply(&rule, batch, 1);
+
+        for coeff in layer.get_coefficients() {
+            assert!((coeff - 0.5f32 * (1.0 - 0.1 * 0.1)).abs() < 0.00001);
+        }
+    }
+
+    #[test]
+    fn compute_batch_matches_compute() {
+        let layer = FeedforwardLayer::new_from(5, 11, sigmoid(), {
+            let mut acc = 0;
+            move || { acc += 1; (1.0f32 + ((13*acc) % 12) as f32) / 13.0f32 }
+        });
+        let inputs: Vec<Vec<f32>> = (0..20)
+            .map(|n| (0..5).map(|i| (n*5 + i) as f32 * 0.1).collect())
+            .collect();
+        let refs: Vec<&[f32]> = inputs.iter().map(|v| v.as_slice()).collect();
+
+        let batched = layer.compute_batch(&refs);
+        for (input, output) in inputs.iter().zip(batched.iter()) {
+            let expected = layer.compute(input);
+            for (a, b) in output.iter().zip(expected.iter()) {
+                assert!((a - b).abs() < 0.0001);
+            }
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn save_and_load_round_trip() {
+        let layer = FeedforwardLayer::new_from(4, 2, sigmoid(), {
+            let mut acc = 0;
+            move || { acc += 1; (1.0f32 + ((13*acc) % 12) as f32) / 13.0f32 }
+        });
+        let path = ::std::env::temp_dir().join("silinapse-save-and-load-round-trip.json");
+
+        layer.save_to(&path).unwrap();
+        let loaded = FeedforwardLayer::load_from(&path).unwrap();
+        ::std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(layer.get_coefficients(), loaded.get_coefficients());
+        assert_eq!(layer.get_biases(), loaded.get_biases());
+        assert_eq!(layer.compute(&[1.0, 1.0, 1.0, 1.0]), loaded.compute(&[1.0, 1.0, 1.0, 1.0]));
+    }
 }
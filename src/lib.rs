@@ -0,0 +1,118 @@
+//! silinapse: small, composable building blocks for feed-forward neural
+//! networks.
+//!
+//! The crate is organised around three core traits:
+//!
+//! - [`Compute`] turns an input vector into an output vector.
+//! - [`SupervisedTrain`] fits a unit to a single `(input, target)` example
+//!   using some training rule `R`.
+//! - [`BackpropTrain`] is the backpropagation counterpart: it propagates an
+//!   error signal through a unit, updates its parameters, and returns the
+//!   error signal for the unit that feeds it.
+//!
+//! plus [`MiniBatchTrain`], for accumulating gradients over a mini-batch,
+//! and [`Mode`], for switching units between training and inference.
+//!
+//! Layers (see [`feedforward`]) and combinators (see [`util`]) implement
+//! these traits so that networks can be assembled by composition; [`mlp`]
+//! builds a whole stack of layers from a list of sizes.
+//!
+//! This crate is vendored as source (no `Cargo.toml` of its own) into
+//! whatever workspace embeds it; the embedding manifest needs `num = "0.1"`
+//! and, to enable the `serde` feature, `serde`/`serde_json` plus a
+//! `serde = []` feature entry forwarding to this crate's `#[cfg(feature =
+//! "serde")]` gates.
+
+extern crate num;
+
+use num::Float;
+
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(feature = "serde")]
+extern crate serde_json;
+
+pub mod activations;
+pub mod cost;
+pub mod dropout;
+pub mod feedforward;
+pub mod mlp;
+pub mod softmax;
+pub mod trainer;
+pub mod training;
+pub mod util;
+
+/// Anything able to turn an input vector into an output vector.
+pub trait Compute<F> {
+    /// Computes the output of this unit for the given input.
+    fn compute(&self, input: &[F]) -> Vec<F>;
+
+    /// The expected length of the `input` slice passed to [`Compute::compute`].
+    fn input_size(&self) -> usize;
+
+    /// The length of the vector returned by [`Compute::compute`].
+    fn output_size(&self) -> usize;
+}
+
+/// A unit that can be fitted to a single `(input, target)` example using a
+/// training rule `R`.
+pub trait SupervisedTrain<F, R> {
+    /// Adjusts this unit's parameters for one `(input, target)` example.
+    fn supervised_train(&mut self, rule: &R, input: &[F], target: &[F]);
+}
+
+/// A unit that can be trained by backpropagating an error signal.
+///
+/// Unlike [`SupervisedTrain`], the second slice is not the target output but
+/// the error signal `∂L/∂Y` flowing back from whatever consumes this unit's
+/// output. The returned vector is `∂L/∂X`, the error signal for whatever
+/// produced this unit's input, so that composite units (see [`util::Chain`])
+/// can thread it backwards layer by layer.
+pub trait BackpropTrain<F, R> {
+    /// Updates this unit's parameters given the incoming error signal, and
+    /// returns the error signal to propagate to the previous unit.
+    fn backprop_train(&mut self, rule: &R, input: &[F], error: &[F]) -> Vec<F>;
+}
+
+/// A unit whose parameter gradient for a single example can be accumulated
+/// into a scratch buffer and applied as one averaged update, for mini-batch
+/// training (see [`trainer::Trainer`]).
+///
+/// This splits [`BackpropTrain::backprop_train`]'s single step into two:
+/// [`MiniBatchTrain::accumulate`] adds one example's gradient into `batch`
+/// without touching this unit's parameters, and [`MiniBatchTrain::apply`]
+/// averages everything accumulated so far and applies it once.
+pub trait MiniBatchTrain<F: Float, R> {
+    /// Scratch space shaped like this unit's parameters.
+    type Batch;
+
+    /// A zeroed scratch buffer for accumulating a mini-batch's gradients.
+    fn new_batch(&self) -> Self::Batch;
+
+    /// Adds this example's gradient into `batch`, and returns the error
+    /// signal `∂L/∂X` to propagate to the previous unit, as in
+    /// [`BackpropTrain::backprop_train`].
+    fn accumulate(&self, rule: &R, input: &[F], error: &[F], batch: &mut Self::Batch) -> Vec<F>;
+
+    /// Applies `batch`'s accumulated gradient, averaged over `count`
+    /// examples, updating this unit's parameters.
+    fn apply(&mut self, rule: &R, batch: Self::Batch, count: usize);
+
+    /// This unit's current regularization penalty under `rule`, for folding
+    /// into a reported cost (see [`trainer::Trainer`]). `0` by default; units
+    /// with regularized parameters (e.g. `feedforward::FeedforwardLayer`)
+    /// override this.
+    fn regularization_penalty(&self, _rule: &R) -> F {
+        F::zero()
+    }
+}
+
+/// A unit whose computation differs between training and inference, such as
+/// [`dropout::Dropout`]. Composite units like [`util::Chain`] implement this
+/// by delegating to every unit they contain, so a whole network can be
+/// switched over with a single call.
+pub trait Mode {
+    /// Switches this unit between training (`true`) and inference (`false`)
+    /// behavior.
+    fn set_train(&mut self, train: bool);
+}
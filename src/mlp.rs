@@ -0,0 +1,225 @@
+//! A high-level builder assembling a multilayer perceptron from layer sizes.
+
+use num::Float;
+
+use {BackpropTrain, Compute, MiniBatchTrain, Mode, SupervisedTrain};
+use activations::ActivationKind;
+use cost::Cost;
+use feedforward::FeedforwardLayer;
+use training::WithCost;
+
+/// A weight-initialization strategy for [`Mlp::new`].
+pub enum Init {
+    /// Xavier/Glorot uniform initialization,
+    /// `weights ~ U(-sqrt(6/(fan_in+fan_out)), +sqrt(6/(fan_in+fan_out)))`.
+    /// A good default for `sigmoid`/`identity` activations.
+    Xavier,
+    /// Gaussian initialization with `std = sqrt(2/fan_in)`, sampled via the
+    /// Box-Muller transform. A good default for `relu`.
+    He,
+}
+
+impl Init {
+    fn sample<F: Float, G: FnMut() -> F>(&self, fan_in: usize, fan_out: usize, generator: &mut G) -> F {
+        match *self {
+            Init::Xavier => {
+                let bound = (F::from(6.0).unwrap() / F::from(fan_in + fan_out).unwrap()).sqrt();
+                let uniform = generator() * F::from(2.0).unwrap() - F::one();
+                uniform * bound
+            }
+            Init::He => {
+                let std = (F::from(2.0).unwrap() / F::from(fan_in).unwrap()).sqrt();
+                let u1 = generator().max(F::from(1e-9).unwrap());
+                let u2 = generator();
+                let two_pi = F::from(2.0 * ::std::f64::consts::PI).unwrap();
+                let radius = (F::from(-2.0).unwrap() * u1.ln()).sqrt();
+                radius * (two_pi * u2).cos() * std
+            }
+        }
+    }
+}
+
+/// A multilayer perceptron assembled from a list of layer sizes, instead of
+/// manually nesting `Chain::new(FeedforwardLayer::new_from(...), ...)`.
+///
+/// Unlike [`crate::util::Chain`], which pairs exactly two units, `Mlp` holds
+/// an arbitrary number of homogeneously-typed layers in a `Vec`, which keeps
+/// its `Compute`/`BackpropTrain`/`SupervisedTrain` impls simple regardless of
+/// depth.
+///
+/// That homogeneity is also its limit: every layer must be a
+/// `FeedforwardLayer`, so an `Mlp` can't terminate in a
+/// [`crate::softmax::SoftmaxLayer`] or interleave a
+/// [`crate::dropout::Dropout`] layer. Reach for `Chain` (nesting as many
+/// levels as needed) when a network needs a mix of layer types.
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct Mlp<F: Float> {
+    layers: Vec<FeedforwardLayer<F, fn(F) -> F, fn(F) -> F>>,
+}
+
+impl<F: Float> Mlp<F> {
+    /// Builds a network with `sizes.len() - 1` layers, the `i`-th one going
+    /// from `sizes[i]` to `sizes[i+1]` inputs/outputs, all sharing
+    /// `activation` and weights/biases drawn via `init` from `generator`
+    /// (expected to yield uniform values in `[0, 1)`).
+    pub fn new<G>(sizes: &[usize], activation: ActivationKind, init: Init, mut generator: G) -> Mlp<F>
+        where G: FnMut() -> F
+    {
+        let mut layers = Vec::with_capacity(sizes.len().saturating_sub(1));
+        for w in sizes.windows(2) {
+            let (fan_in, fan_out) = (w[0], w[1]);
+            let layer = FeedforwardLayer::new_from(fan_in, fan_out, activation.build(),
+                || init.sample(fan_in, fan_out, &mut generator));
+            layers.push(layer);
+        }
+        Mlp { layers: layers }
+    }
+
+    pub fn layers(&self) -> &[FeedforwardLayer<F, fn(F) -> F, fn(F) -> F>] {
+        &self.layers
+    }
+}
+
+impl<F: Float> Compute<F> for Mlp<F> {
+    fn compute(&self, input: &[F]) -> Vec<F> {
+        let mut current = input.to_owned();
+        for layer in &self.layers {
+            current = layer.compute(&current);
+        }
+        current
+    }
+
+    fn input_size(&self) -> usize {
+        self.layers.first().map(|l| l.input_size()).unwrap_or(0)
+    }
+
+    fn output_size(&self) -> usize {
+        self.layers.last().map(|l| l.output_size()).unwrap_or(0)
+    }
+}
+
+impl<F: Float> Mode for Mlp<F> {
+    /// Delegates to every layer; none of them are currently mode-sensitive,
+    /// but this keeps `Mlp` composable with `Dropout` should a future layer
+    /// list mix the two.
+    fn set_train(&mut self, train: bool) {
+        for layer in &mut self.layers {
+            layer.set_train(train);
+        }
+    }
+}
+
+impl<F: Float, R> BackpropTrain<F, R> for Mlp<F>
+    where FeedforwardLayer<F, fn(F) -> F, fn(F) -> F>: BackpropTrain<F, R>
+{
+    fn backprop_train(&mut self, rule: &R, input: &[F], error: &[F]) -> Vec<F> {
+        let mut activations = Vec::with_capacity(self.layers.len() + 1);
+        activations.push(input.to_owned());
+        for layer in &self.layers {
+            let out = layer.compute(activations.last().unwrap());
+            activations.push(out);
+        }
+
+        let mut propagated = error.to_owned();
+        for (i, layer) in self.layers.iter_mut().enumerate().rev() {
+            propagated = layer.backprop_train(rule, &activations[i], &propagated);
+        }
+        propagated
+    }
+}
+
+impl<F: Float, R> SupervisedTrain<F, R> for Mlp<F>
+    where R: WithCost<F>,
+          Mlp<F>: BackpropTrain<F, R>
+{
+    fn supervised_train(&mut self, rule: &R, input: &[F], target: &[F]) {
+        let output = self.compute(input);
+        let error = rule.cost().grad(&output, target);
+        self.backprop_train(rule, input, &error);
+    }
+}
+
+impl<F: Float, R> MiniBatchTrain<F, R> for Mlp<F>
+    where FeedforwardLayer<F, fn(F) -> F, fn(F) -> F>: MiniBatchTrain<F, R>
+{
+    type Batch = Vec<<FeedforwardLayer<F, fn(F) -> F, fn(F) -> F> as MiniBatchTrain<F, R>>::Batch>;
+
+    fn new_batch(&self) -> Self::Batch {
+        self.layers.iter().map(|l| l.new_batch()).collect()
+    }
+
+    fn accumulate(&self, rule: &R, input: &[F], error: &[F], batch: &mut Self::Batch) -> Vec<F> {
+        let mut activations = Vec::with_capacity(self.layers.len() + 1);
+        activations.push(input.to_owned());
+        for layer in &self.layers {
+            let out = layer.compute(activations.last().unwrap());
+            activations.push(out);
+        }
+
+        let mut propagated = error.to_owned();
+        for (i, (layer, layer_batch)) in self.layers.iter().zip(batch.iter_mut()).enumerate().rev() {
+            propagated = layer.accumulate(rule, &activations[i], &propagated, layer_batch);
+        }
+        propagated
+    }
+
+    fn apply(&mut self, rule: &R, batch: Self::Batch, count: usize) {
+        for (layer, layer_batch) in self.layers.iter_mut().zip(batch.into_iter()) {
+            layer.apply(rule, layer_batch, count);
+        }
+    }
+
+    fn regularization_penalty(&self, rule: &R) -> F {
+        self.layers.iter().fold(F::zero(), |acc, layer| acc + layer.regularization_penalty(rule))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {Compute, SupervisedTrain};
+    use activations::ActivationKind;
+    use training::GradientDescent;
+
+    use super::{Init, Mlp};
+
+    #[test]
+    fn assembles_matching_layer_sizes() {
+        let mlp = Mlp::<f32>::new(&[3, 5, 11, 7, 3], ActivationKind::Sigmoid, Init::Xavier, {
+            let mut acc = 0;
+            move || { acc += 1; (1.0f32 + ((13*acc) % 12) as f32) / 13.0f32 }
+        });
+        assert_eq!(mlp.layers().len(), 4);
+        assert_eq!(mlp.input_size(), 3);
+        assert_eq!(mlp.output_size(), 3);
+        assert_eq!(mlp.compute(&[1.0, 1.0, 1.0]).len(), 3);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serializes_as_json_and_round_trips() {
+        let mlp = Mlp::<f32>::new(&[4, 8, 2], ActivationKind::Sigmoid, Init::Xavier, {
+            let mut acc = 0;
+            move || { acc += 1; (1.0f32 + ((13*acc) % 12) as f32) / 13.0f32 }
+        });
+
+        let json = ::serde_json::to_string(&mlp).unwrap();
+        let loaded: Mlp<f32> = ::serde_json::from_str(&json).unwrap();
+
+        assert_eq!(mlp.compute(&[1.0, 1.0, 1.0, 1.0]), loaded.compute(&[1.0, 1.0, 1.0, 1.0]));
+    }
+
+    #[test]
+    fn trains_with_gradient_descent() {
+        let mut mlp = Mlp::<f32>::new(&[4, 8, 2], ActivationKind::Sigmoid, Init::Xavier, {
+            let mut acc = 0;
+            move || { acc += 1; (1.0f32 + ((13*acc) % 12) as f32) / 13.0f32 }
+        });
+        let rule = GradientDescent::new(0.5f32);
+        for _ in 0..200 {
+            mlp.supervised_train(&rule, &[1.0, 1.0, 1.0, 1.0], &[1.0, 0.0]);
+            mlp.supervised_train(&rule, &[1.0, -1.0, 1.0, -1.0], &[0.0, 1.0]);
+        }
+        assert!({ let out = mlp.compute(&[1.0, 1.0, 1.0, 1.0]); out[0] > 0.8 && out[1] < 0.2 });
+        assert!({ let out = mlp.compute(&[1.0, -1.0, 1.0, -1.0]); out[0] < 0.2 && out[1] > 0.8 });
+    }
+}
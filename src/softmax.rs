@@ -0,0 +1,261 @@
+//! A dense output layer with a joint softmax activation, for classification.
+
+use std::cmp::min;
+
+use num::{Float, zero};
+
+use {BackpropTrain, Compute, MiniBatchTrain, Mode, SupervisedTrain};
+use cost::Cost;
+use training::{GradientDescent, WithCost};
+
+/// A dense layer whose outputs are jointly normalized into a probability
+/// distribution via softmax, rather than activated component-wise like
+/// [`crate::feedforward::FeedforwardLayer`].
+///
+/// `compute` shifts by the maximum pre-activation before exponentiating, for
+/// numerical stability: `exp(x_j - max) / sum_k exp(x_k - max)`.
+pub struct SoftmaxLayer<F: Float> {
+    inputs: usize,
+    coeffs: Vec<F>,
+    biases: Vec<F>,
+}
+
+impl<F: Float> SoftmaxLayer<F> {
+    /// Creates a new softmax layer with all its weights and biases
+    /// generated by the provided closure.
+    pub fn new_from<G>(inputs: usize, outputs: usize, mut generator: G) -> SoftmaxLayer<F>
+        where G: FnMut() -> F
+    {
+        SoftmaxLayer {
+            inputs: inputs,
+            coeffs: (0..inputs*outputs).map(|_| generator()).collect(),
+            biases: (0..outputs).map(|_| generator()).collect(),
+        }
+    }
+
+    pub fn get_coefficients(&self) -> &Vec<F> {
+        &self.coeffs
+    }
+
+    pub fn get_biases(&self) -> &Vec<F> {
+        &self.biases
+    }
+}
+
+impl<F: Float> Mode for SoftmaxLayer<F> {
+    /// A no-op: this layer's computation doesn't depend on training/eval
+    /// mode, but the impl lets it sit alongside mode-sensitive units (e.g.
+    /// `dropout::Dropout`) inside a `Chain`.
+    fn set_train(&mut self, _train: bool) {}
+}
+
+impl<F: Float> Compute<F> for SoftmaxLayer<F> {
+    fn compute(&self, input: &[F]) -> Vec<F> {
+        let mut preactivation = self.biases.clone();
+        for j in 0..self.biases.len() {
+            for i in 0..min(self.inputs, input.len()) {
+                preactivation[j] = preactivation[j] + self.coeffs[j*self.inputs + i] * input[i]
+            }
+        }
+
+        let max = preactivation.iter().fold(F::neg_infinity(), |acc, &x| acc.max(x));
+        let mut exps: Vec<F> = preactivation.iter().map(|&x| (x - max).exp()).collect();
+        let sum = exps.iter().fold(F::zero(), |acc, &x| acc + x);
+        for e in &mut exps {
+            *e = *e / sum;
+        }
+        exps
+    }
+
+    fn input_size(&self) -> usize {
+        self.inputs
+    }
+
+    fn output_size(&self) -> usize {
+        self.biases.len()
+    }
+}
+
+impl<F: Float, C> BackpropTrain<F, GradientDescent<F, C>> for SoftmaxLayer<F> {
+    /// Propagates the incoming error signal through the softmax Jacobian
+    /// (`delta[j] = y[j] * (error[j] - sum_k error[k]*y[k])`), updates this
+    /// layer's weights and biases, and returns `∂L/∂X` for the previous
+    /// layer.
+    ///
+    /// Invariant: when `error` is [`crate::cost::CrossEntropy::grad`]'s
+    /// output and `target` sums to `1`, this simplifies algebraically to
+    /// `delta[j] = output[j] - target[j]` — the standard combined
+    /// softmax+cross-entropy gradient (see the `combined_gradient_matches_shortcut`
+    /// test). It is only correct for that pairing; a different cost paired
+    /// with this layer will propagate a mathematically valid but different
+    /// gradient.
+    fn backprop_train(&mut self, rule: &GradientDescent<F, C>, input: &[F], error: &[F]) -> Vec<F> {
+        let output = self.compute(input);
+        let weighted = output.iter().zip(error.iter())
+            .fold(F::zero(), |acc, (&y, &e)| acc + y * e);
+        let deltas: Vec<F> = output.iter().zip(error.iter())
+            .map(|(&y, &e)| y * (e - weighted))
+            .collect();
+
+        let mut propagated = vec![zero(); self.inputs];
+        for j in 0..self.biases.len() {
+            for i in 0..min(self.inputs, input.len()) {
+                propagated[i] = propagated[i] + self.coeffs[i + j*self.inputs] * deltas[j];
+            }
+        }
+
+        for j in 0..self.biases.len() {
+            for i in 0..min(self.inputs, input.len()) {
+                self.coeffs[i + j*self.inputs] =
+                    self.coeffs[i + j*self.inputs] - rule.rate * input[i] * deltas[j];
+            }
+            self.biases[j] = self.biases[j] - rule.rate * deltas[j];
+        }
+        propagated
+    }
+}
+
+impl<F: Float, C: Cost<F>> SupervisedTrain<F, GradientDescent<F, C>> for SoftmaxLayer<F> {
+    fn supervised_train(&mut self, rule: &GradientDescent<F, C>, input: &[F], target: &[F]) {
+        let output = self.compute(input);
+        let error = rule.cost().grad(&output, target);
+        self.backprop_train(rule, input, &error);
+    }
+}
+
+/// Scratch buffers for [`SoftmaxLayer`]'s [`MiniBatchTrain`] impl, shaped
+/// like its `coeffs`/`biases`.
+pub struct SoftmaxBatch<F> {
+    coeff_grad: Vec<F>,
+    bias_grad: Vec<F>,
+}
+
+impl<F: Float, C> MiniBatchTrain<F, GradientDescent<F, C>> for SoftmaxLayer<F> {
+    type Batch = SoftmaxBatch<F>;
+
+    fn new_batch(&self) -> SoftmaxBatch<F> {
+        SoftmaxBatch {
+            coeff_grad: vec![zero(); self.coeffs.len()],
+            bias_grad: vec![zero(); self.biases.len()],
+        }
+    }
+
+    fn accumulate(&self,
+                 _rule: &GradientDescent<F, C>,
+                 input: &[F],
+                 error: &[F],
+                 batch: &mut SoftmaxBatch<F>)
+        -> Vec<F>
+    {
+        let output = self.compute(input);
+        let weighted = output.iter().zip(error.iter())
+            .fold(F::zero(), |acc, (&y, &e)| acc + y * e);
+        let deltas: Vec<F> = output.iter().zip(error.iter())
+            .map(|(&y, &e)| y * (e - weighted))
+            .collect();
+
+        let mut propagated = vec![zero(); self.inputs];
+        for j in 0..self.biases.len() {
+            for i in 0..min(self.inputs, input.len()) {
+                propagated[i] = propagated[i] + self.coeffs[i + j*self.inputs] * deltas[j];
+            }
+        }
+
+        for j in 0..self.biases.len() {
+            for i in 0..min(self.inputs, input.len()) {
+                batch.coeff_grad[i + j*self.inputs] = batch.coeff_grad[i + j*self.inputs] + input[i] * deltas[j];
+            }
+            batch.bias_grad[j] = batch.bias_grad[j] + deltas[j];
+        }
+        propagated
+    }
+
+    fn apply(&mut self, rule: &GradientDescent<F, C>, batch: SoftmaxBatch<F>, count: usize) {
+        let n = F::from(count).unwrap();
+        for (idx, grad) in batch.coeff_grad.into_iter().enumerate() {
+            self.coeffs[idx] = self.coeffs[idx] - rule.rate * grad / n;
+        }
+        for (j, grad) in batch.bias_grad.into_iter().enumerate() {
+            self.biases[j] = self.biases[j] - rule.rate * grad / n;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use Compute;
+    use activations::identity;
+    use cost::{Cost, CrossEntropy};
+    use feedforward::FeedforwardLayer;
+    use trainer::Trainer;
+    use training::GradientDescent;
+    use util::Chain;
+
+    use super::SoftmaxLayer;
+
+    #[test]
+    fn compute_sums_to_one() {
+        let layer = SoftmaxLayer::new_from(3, 3, || 0.5f32);
+        let output = layer.compute(&[1.0, 2.0, 3.0]);
+        let sum: f32 = output.iter().sum();
+        assert!((sum - 1.0).abs() < 0.0001);
+    }
+
+    #[test]
+    fn combined_gradient_matches_shortcut() {
+        use BackpropTrain;
+
+        let mut layer = SoftmaxLayer::new_from(3, 3, {
+            let mut acc = 0;
+            move || { acc += 1; (1.0f32 + ((7*acc) % 5) as f32) / 5.0f32 }
+        });
+        let input = [1.0f32, -0.5, 0.25];
+        let target = [0.0f32, 1.0, 0.0];
+
+        let output = layer.compute(&input);
+        let error = CrossEntropy.grad(&output, &target);
+
+        // recompute deltas exactly as backprop_train does, without mutating
+        // the layer, to compare against the documented shortcut.
+        let weighted: f32 = output.iter().zip(error.iter()).map(|(&y, &e)| y * e).sum();
+        let deltas: Vec<f32> = output.iter().zip(error.iter()).map(|(&y, &e)| y * (e - weighted)).collect();
+
+        for (delta, (&y, &t)) in deltas.iter().zip(output.iter().zip(target.iter())) {
+            assert!((delta - (y - t)).abs() < 0.0001);
+        }
+
+        // also exercise the real training path end to end.
+        let rule = GradientDescent::new(0.1f32);
+        layer.backprop_train(&rule, &input, &error);
+    }
+
+    #[test]
+    fn trains_with_trainer_through_a_chain() {
+        // the mini-batch Trainer must be able to drive a network ending in a
+        // softmax output, for multi-class targets.
+        let mut net = Chain::new(
+            FeedforwardLayer::new_from(3, 4, identity(), {
+                let mut acc = 0;
+                move || { acc += 1; (1.0f32 + ((13*acc) % 12) as f32) / 13.0f32 }
+            }),
+            SoftmaxLayer::new_from(4, 3, {
+                let mut acc = 0;
+                move || { acc += 1; (1.0f32 + ((7*acc) % 10) as f32) / 10.0f32 }
+            }),
+        );
+
+        let rule = GradientDescent::with_cost(0.5f32, CrossEntropy);
+        let mut data = vec![
+            (vec![1.0f32, 0.0, 0.0], vec![1.0f32, 0.0, 0.0]),
+            (vec![0.0f32, 1.0, 0.0], vec![0.0f32, 1.0, 0.0]),
+            (vec![0.0f32, 0.0, 1.0], vec![0.0f32, 0.0, 1.0]),
+        ];
+
+        let mut costs = Vec::new();
+        Trainer::new(3, 100)
+            .on_error(|_, cost| costs.push(cost))
+            .fit(&mut net, &rule, &mut data, &mut |n| n.saturating_sub(1));
+
+        assert!(costs.last().unwrap() < &costs[0]);
+    }
+}
@@ -0,0 +1,230 @@
+//! Epoch-based training over a whole dataset, as opposed to the single-example
+//! updates driven directly by [`crate::SupervisedTrain`].
+
+use num::Float;
+
+use {Compute, MiniBatchTrain};
+use cost::Cost;
+use training::WithCost;
+
+/// Trains a unit over a dataset for a fixed number of epochs, splitting each
+/// epoch into mini-batches whose per-example gradients are accumulated (via
+/// [`MiniBatchTrain`]) and applied as a single averaged update.
+///
+/// Shuffling and progress reporting are configured with the builder methods
+/// below; shuffling is on and both callbacks are unset by default, so
+/// `Trainer::new(32, 10).fit(...)` is enough to get going.
+pub struct Trainer<'a, F> {
+    batch_size: usize,
+    epochs: usize,
+    shuffle: bool,
+    on_epoch: Option<Box<FnMut(usize) + 'a>>,
+    on_error: Option<Box<FnMut(usize, F) + 'a>>,
+}
+
+impl<'a, F: Float> Trainer<'a, F> {
+    /// A trainer running `epochs` passes over the dataset, with `batch_size`
+    /// examples accumulated per update, shuffling the data every epoch.
+    pub fn new(batch_size: usize, epochs: usize) -> Trainer<'a, F> {
+        Trainer {
+            batch_size: batch_size,
+            epochs: epochs,
+            shuffle: true,
+            on_epoch: None,
+            on_error: None,
+        }
+    }
+
+    /// Whether to shuffle the dataset at the start of every epoch (default
+    /// `true`).
+    pub fn with_shuffle(mut self, shuffle: bool) -> Trainer<'a, F> {
+        self.shuffle = shuffle;
+        self
+    }
+
+    /// Called with the epoch index (starting at `0`) once it completes.
+    pub fn on_epoch<CB: FnMut(usize) + 'a>(mut self, callback: CB) -> Trainer<'a, F> {
+        self.on_epoch = Some(Box::new(callback));
+        self
+    }
+
+    /// Called with the epoch index and the dataset's mean cost, plus the
+    /// unit's regularization penalty under the rule, once the epoch
+    /// completes.
+    pub fn on_error<CB: FnMut(usize, F) + 'a>(mut self, callback: CB) -> Trainer<'a, F> {
+        self.on_error = Some(Box::new(callback));
+        self
+    }
+
+    /// Trains `unit` over `data`, mutating it in place. `random(n)` must
+    /// return a uniformly random index in `0..n`, and drives the per-epoch
+    /// shuffle (a Fisher-Yates shuffle) instead of pulling in a dependency on
+    /// a particular RNG crate.
+    pub fn fit<T, R>(&mut self,
+                     unit: &mut T,
+                     rule: &R,
+                     data: &mut [(Vec<F>, Vec<F>)],
+                     random: &mut FnMut(usize) -> usize)
+        where T: Compute<F> + MiniBatchTrain<F, R>,
+              R: WithCost<F>
+    {
+        if data.is_empty() {
+            return;
+        }
+
+        let batch_size = if self.batch_size == 0 { data.len() } else { self.batch_size };
+
+        for epoch in 0..self.epochs {
+            if self.shuffle {
+                shuffle(data, random);
+            }
+
+            let mut total_cost = F::zero();
+            for chunk in data.chunks(batch_size) {
+                if chunk.is_empty() {
+                    continue;
+                }
+                let mut batch = unit.new_batch();
+                for &(ref input, ref target) in chunk {
+                    let output = unit.compute(input);
+                    total_cost = total_cost + rule.cost().eval(&output, target);
+                    let error = rule.cost().grad(&output, target);
+                    unit.accumulate(rule, input, &error, &mut batch);
+                }
+                unit.apply(rule, batch, chunk.len());
+            }
+
+            if let Some(ref mut on_epoch) = self.on_epoch {
+                on_epoch(epoch);
+            }
+            if let Some(ref mut on_error) = self.on_error {
+                let mean_cost = total_cost / F::from(data.len()).unwrap();
+                on_error(epoch, mean_cost + unit.regularization_penalty(rule));
+            }
+        }
+    }
+}
+
+/// An in-place Fisher-Yates shuffle driven by an externally supplied source
+/// of randomness, so this crate doesn't need to depend on a particular RNG.
+fn shuffle<T>(data: &mut [T], random: &mut FnMut(usize) -> usize) {
+    for i in (1..data.len()).rev() {
+        let j = random(i + 1);
+        data.swap(i, j);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use activations::{identity, ActivationKind};
+    use feedforward::FeedforwardLayer;
+    use mlp::{Init, Mlp};
+    use training::{Adam, GradientDescent, Momentum, Regularization};
+    use Compute;
+
+    use super::Trainer;
+
+    fn lcg(seed: &mut u32) -> usize {
+        *seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+        *seed as usize
+    }
+
+    #[test]
+    fn fit_reduces_cost_and_reports_epochs() {
+        let mut mlp = Mlp::<f32>::new(&[4, 8, 2], ActivationKind::Sigmoid, Init::Xavier, {
+            let mut acc = 0;
+            move || { acc += 1; (1.0f32 + ((13*acc) % 12) as f32) / 13.0f32 }
+        });
+        let rule = GradientDescent::new(0.5f32);
+        let mut data = vec![
+            (vec![1.0f32, 1.0, 1.0, 1.0], vec![1.0f32, 0.0]),
+            (vec![1.0f32, -1.0, 1.0, -1.0], vec![0.0f32, 1.0]),
+        ];
+
+        let mut seed = 42u32;
+        let mut epochs_seen = 0;
+        let mut costs = Vec::new();
+        Trainer::new(2, 200)
+            .on_epoch(|_| epochs_seen += 1)
+            .on_error(|_, cost| costs.push(cost))
+            .fit(&mut mlp, &rule, &mut data, &mut |n| lcg(&mut seed) % n);
+
+        assert_eq!(epochs_seen, 200);
+        assert_eq!(costs.len(), 200);
+        assert!(costs.last().unwrap() < &costs[0]);
+
+        assert!({ let out = mlp.compute(&[1.0, 1.0, 1.0, 1.0]); out[0] > 0.8 && out[1] < 0.2 });
+        assert!({ let out = mlp.compute(&[1.0, -1.0, 1.0, -1.0]); out[0] < 0.2 && out[1] > 0.8 });
+    }
+
+    #[test]
+    fn reported_cost_includes_the_regularization_penalty() {
+        let mut plain = FeedforwardLayer::new_from(4, 2, identity(), || 0.5f32);
+        let mut regularized = FeedforwardLayer::new_from(4, 2, identity(), || 0.5f32);
+        let mut data = vec![(vec![1.0f32, 1.0, 1.0, 1.0], vec![0.0f32, 0.0])];
+
+        let plain_rule = GradientDescent::new(0.1f32);
+        let regularized_rule = GradientDescent::new(0.1f32).with_regularization(Regularization::L2(0.1f32));
+
+        let mut plain_cost = 0.0f32;
+        Trainer::new(0, 1)
+            .on_error(|_, cost| plain_cost = cost)
+            .fit(&mut plain, &plain_rule, &mut data, &mut |n| n.saturating_sub(1));
+
+        let mut regularized_cost = 0.0f32;
+        Trainer::new(0, 1)
+            .on_error(|_, cost| regularized_cost = cost)
+            .fit(&mut regularized, &regularized_rule, &mut data, &mut |n| n.saturating_sub(1));
+
+        assert!(regularized_cost > plain_cost);
+    }
+
+    #[test]
+    fn fit_drives_momentum_and_adam() {
+        // Trainer::fit must type-check and actually reduce cost with every
+        // optimizer, not just GradientDescent.
+        let mut data = vec![
+            (vec![1.0f32, 1.0, 1.0, 1.0], vec![1.0f32, 0.0]),
+            (vec![1.0f32, -1.0, 1.0, -1.0], vec![0.0f32, 1.0]),
+        ];
+
+        let mut momentum_mlp = Mlp::<f32>::new(&[4, 8, 2], ActivationKind::Sigmoid, Init::Xavier, {
+            let mut acc = 0;
+            move || { acc += 1; (1.0f32 + ((13*acc) % 12) as f32) / 13.0f32 }
+        });
+        let momentum_rule = Momentum::new(0.3f32, 0.9f32);
+        let mut momentum_costs = Vec::new();
+        Trainer::new(2, 200)
+            .on_error(|_, cost| momentum_costs.push(cost))
+            .fit(&mut momentum_mlp, &momentum_rule, &mut data, &mut |n| n.saturating_sub(1));
+        assert!(momentum_costs.last().unwrap() < &momentum_costs[0]);
+
+        let mut adam_mlp = Mlp::<f32>::new(&[4, 8, 2], ActivationKind::Sigmoid, Init::Xavier, {
+            let mut acc = 0;
+            move || { acc += 1; (1.0f32 + ((13*acc) % 12) as f32) / 13.0f32 }
+        });
+        let adam_rule = Adam::new(0.1f32);
+        let mut adam_costs = Vec::new();
+        Trainer::new(2, 200)
+            .on_error(|_, cost| adam_costs.push(cost))
+            .fit(&mut adam_mlp, &adam_rule, &mut data, &mut |n| n.saturating_sub(1));
+        assert!(adam_costs.last().unwrap() < &adam_costs[0]);
+    }
+
+    #[test]
+    fn fit_with_empty_data_does_not_panic() {
+        let mut mlp = Mlp::<f32>::new(&[4, 8, 2], ActivationKind::Sigmoid, Init::Xavier, {
+            let mut acc = 0;
+            move || { acc += 1; (1.0f32 + ((13*acc) % 12) as f32) / 13.0f32 }
+        });
+        let rule = GradientDescent::new(0.5f32);
+        let mut data: Vec<(Vec<f32>, Vec<f32>)> = Vec::new();
+
+        let mut epochs_seen = 0;
+        Trainer::new(0, 5)
+            .on_epoch(|_| epochs_seen += 1)
+            .fit(&mut mlp, &rule, &mut data, &mut |n| n.saturating_sub(1));
+
+        assert_eq!(epochs_seen, 0);
+    }
+}
@@ -0,0 +1,211 @@
+//! Training rules used by `SupervisedTrain` and `BackpropTrain` implementors.
+
+use num::Float;
+
+use cost::{Cost, MeanSquared};
+
+/// A training rule that picks the [`Cost`] whose gradient seeds
+/// backpropagation.
+///
+/// Implemented by every backprop-based rule so that a single generic
+/// `SupervisedTrain` impl (see `feedforward::FeedforwardLayer` and
+/// `util::Chain`) can compute the initial error signal regardless of which
+/// optimizer is driving the weight update.
+pub trait WithCost<F> {
+    type Cost: Cost<F>;
+
+    fn cost(&self) -> &Self::Cost;
+}
+
+/// The classic perceptron learning rule.
+pub struct PerceptronRule<F> {
+    pub rate: F,
+}
+
+/// An L1 or L2 penalty applied to a layer's weights on every update,
+/// mirroring rusty-machine's `Regularization`. Biases are conventionally
+/// left out of the penalty.
+pub enum Regularization<F> {
+    L1(F),
+    L2(F),
+}
+
+impl<F: Float> Regularization<F> {
+    /// The amount subtracted from a weight `w` on top of its gradient step:
+    /// `rate * lambda * sign(w)` for L1, `rate * lambda * w` for L2.
+    pub fn weight_decay(&self, rate: F, w: F) -> F {
+        match *self {
+            Regularization::L1(lambda) => rate * lambda * w.signum(),
+            Regularization::L2(lambda) => rate * lambda * w,
+        }
+    }
+
+    /// This weight's contribution to the regularization penalty
+    /// (`lambda * |w|` for L1, `lambda * w^2` for L2), for folding into a
+    /// reported cost.
+    pub fn penalty(&self, w: F) -> F {
+        match *self {
+            Regularization::L1(lambda) => lambda * w.abs(),
+            Regularization::L2(lambda) => lambda * w * w,
+        }
+    }
+}
+
+/// Plain (stochastic) gradient descent.
+///
+/// `cost` picks the loss whose gradient seeds backpropagation; it defaults
+/// to [`MeanSquared`] so existing callers that only set `rate` keep working.
+/// An optional `regularization` adds an L1/L2 weight penalty to every
+/// `feedforward::FeedforwardLayer` weight update; `softmax::SoftmaxLayer`
+/// updates do not apply it.
+pub struct GradientDescent<F, C = MeanSquared> {
+    pub rate: F,
+    pub cost: C,
+    pub regularization: Option<Regularization<F>>,
+}
+
+impl<F> GradientDescent<F, MeanSquared> {
+    /// A gradient descent rule using the mean squared error.
+    pub fn new(rate: F) -> GradientDescent<F, MeanSquared> {
+        GradientDescent { rate: rate, cost: MeanSquared, regularization: None }
+    }
+}
+
+impl<F, C: Cost<F>> GradientDescent<F, C> {
+    /// A gradient descent rule using the given cost function.
+    pub fn with_cost(rate: F, cost: C) -> GradientDescent<F, C> {
+        GradientDescent { rate: rate, cost: cost, regularization: None }
+    }
+
+    /// This rule with an L1/L2 penalty applied to weights on every update.
+    pub fn with_regularization(mut self, regularization: Regularization<F>) -> GradientDescent<F, C> {
+        self.regularization = Some(regularization);
+        self
+    }
+}
+
+impl<F: Float, C> GradientDescent<F, C> {
+    /// The total regularization penalty over a layer's weights, for folding
+    /// into a reported cost. `0` when no regularization is set.
+    pub fn regularization_penalty(&self, coeffs: &[F]) -> F {
+        match self.regularization {
+            Some(ref r) => coeffs.iter().fold(F::zero(), |acc, &w| acc + r.penalty(w)),
+            None => F::zero(),
+        }
+    }
+}
+
+impl<F, C: Cost<F>> WithCost<F> for GradientDescent<F, C> {
+    type Cost = C;
+    fn cost(&self) -> &C { &self.cost }
+}
+
+/// Gradient descent with classic momentum.
+///
+/// `v = mu*v - rate*grad; w += v`. Because `v` must persist between calls,
+/// layers keep a per-parameter velocity accumulator alongside their weights
+/// (see `feedforward::FeedforwardLayer`'s momentum state), sized like
+/// `coeffs`/`biases` and lazily initialized to zero on first use.
+pub struct Momentum<F, C = MeanSquared> {
+    pub rate: F,
+    pub mu: F,
+    pub cost: C,
+}
+
+impl<F> Momentum<F, MeanSquared> {
+    /// Momentum with `mu` around `0.9` and the mean squared error.
+    pub fn new(rate: F, mu: F) -> Momentum<F, MeanSquared> {
+        Momentum { rate: rate, mu: mu, cost: MeanSquared }
+    }
+}
+
+impl<F, C: Cost<F>> Momentum<F, C> {
+    /// Momentum using the given cost function.
+    pub fn with_cost(rate: F, mu: F, cost: C) -> Momentum<F, C> {
+        Momentum { rate: rate, mu: mu, cost: cost }
+    }
+}
+
+impl<F, C: Cost<F>> WithCost<F> for Momentum<F, C> {
+    type Cost = C;
+    fn cost(&self) -> &C { &self.cost }
+}
+
+/// ADAM: adaptive moment estimation.
+///
+/// Keeps per-parameter first and second moment accumulators `m`/`s` and a
+/// per-layer timestep `t` (see `feedforward::FeedforwardLayer`'s ADAM state), updated
+/// each step as `m = b1*m + (1-b1)*g`, `s = b2*s + (1-b2)*g*g`, bias-corrected
+/// to `m_hat`/`s_hat`, with `w -= rate*m_hat/(sqrt(s_hat)+eps)`.
+pub struct Adam<F, C = MeanSquared> {
+    pub rate: F,
+    pub b1: F,
+    pub b2: F,
+    pub eps: F,
+    pub cost: C,
+}
+
+impl<F: Float> Adam<F, MeanSquared> {
+    /// ADAM with the defaults `b1=0.9, b2=0.999, eps=1e-8` and the mean
+    /// squared error.
+    pub fn new(rate: F) -> Adam<F, MeanSquared> {
+        Adam::with_cost(rate, MeanSquared)
+    }
+}
+
+impl<F: Float, C: Cost<F>> Adam<F, C> {
+    /// ADAM with the defaults `b1=0.9, b2=0.999, eps=1e-8` and the given
+    /// cost function.
+    pub fn with_cost(rate: F, cost: C) -> Adam<F, C> {
+        Adam {
+            rate: rate,
+            b1: F::from(0.9).unwrap(),
+            b2: F::from(0.999).unwrap(),
+            eps: F::from(1e-8).unwrap(),
+            cost: cost,
+        }
+    }
+}
+
+impl<F, C: Cost<F>> WithCost<F> for Adam<F, C> {
+    type Cost = C;
+    fn cost(&self) -> &C { &self.cost }
+}
+
+/// Per-parameter velocity accumulated by [`Momentum`], sized like a layer's
+/// `coeffs`/`biases`.
+pub struct MomentumState<F> {
+    pub coeff_velocity: Vec<F>,
+    pub bias_velocity: Vec<F>,
+}
+
+impl<F: Float> MomentumState<F> {
+    pub fn zeros(coeffs: usize, biases: usize) -> MomentumState<F> {
+        MomentumState {
+            coeff_velocity: vec![F::zero(); coeffs],
+            bias_velocity: vec![F::zero(); biases],
+        }
+    }
+}
+
+/// Per-parameter first/second moment estimates and timestep accumulated by
+/// [`Adam`], sized like a layer's `coeffs`/`biases`.
+pub struct AdamState<F> {
+    pub coeff_m: Vec<F>,
+    pub coeff_s: Vec<F>,
+    pub bias_m: Vec<F>,
+    pub bias_s: Vec<F>,
+    pub t: i32,
+}
+
+impl<F: Float> AdamState<F> {
+    pub fn zeros(coeffs: usize, biases: usize) -> AdamState<F> {
+        AdamState {
+            coeff_m: vec![F::zero(); coeffs],
+            coeff_s: vec![F::zero(); coeffs],
+            bias_m: vec![F::zero(); biases],
+            bias_s: vec![F::zero(); biases],
+            t: 0,
+        }
+    }
+}
@@ -0,0 +1,124 @@
+//! Composition helpers for chaining units together.
+
+use num::Float;
+
+use cost::Cost;
+use training::WithCost;
+use {BackpropTrain, Compute, MiniBatchTrain, Mode, SupervisedTrain};
+
+/// Chains two units so that the output of `first` feeds the input of
+/// `second`.
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct Chain<A, B> {
+    first: A,
+    second: B,
+}
+
+impl<A, B> Chain<A, B> {
+    pub fn new(first: A, second: B) -> Chain<A, B> {
+        Chain { first: first, second: second }
+    }
+}
+
+impl<F, A, B> Compute<F> for Chain<A, B>
+    where A: Compute<F>,
+          B: Compute<F>
+{
+    fn compute(&self, input: &[F]) -> Vec<F> {
+        self.second.compute(&self.first.compute(input))
+    }
+
+    fn input_size(&self) -> usize {
+        self.first.input_size()
+    }
+
+    fn output_size(&self) -> usize {
+        self.second.output_size()
+    }
+}
+
+impl<F, R, A, B> BackpropTrain<F, R> for Chain<A, B>
+    where A: Compute<F> + BackpropTrain<F, R>,
+          B: BackpropTrain<F, R>
+{
+    fn backprop_train(&mut self, rule: &R, input: &[F], error: &[F]) -> Vec<F> {
+        let hidden = self.first.compute(input);
+        let hidden_error = self.second.backprop_train(rule, &hidden, error);
+        self.first.backprop_train(rule, input, &hidden_error)
+    }
+}
+
+impl<F, R, A, B> SupervisedTrain<F, R> for Chain<A, B>
+    where A: Compute<F> + BackpropTrain<F, R>,
+          B: Compute<F> + BackpropTrain<F, R>,
+          R: WithCost<F>
+{
+    fn supervised_train(&mut self, rule: &R, input: &[F], target: &[F]) {
+        let output = self.compute(input);
+        let error = rule.cost().grad(&output, target);
+        self.backprop_train(rule, input, &error);
+    }
+}
+
+impl<F: Float, R, A, B> MiniBatchTrain<F, R> for Chain<A, B>
+    where A: Compute<F> + MiniBatchTrain<F, R>,
+          B: Compute<F> + MiniBatchTrain<F, R>
+{
+    type Batch = (A::Batch, B::Batch);
+
+    fn new_batch(&self) -> (A::Batch, B::Batch) {
+        (self.first.new_batch(), self.second.new_batch())
+    }
+
+    fn accumulate(&self, rule: &R, input: &[F], error: &[F], batch: &mut (A::Batch, B::Batch)) -> Vec<F> {
+        let hidden = self.first.compute(input);
+        let hidden_error = self.second.accumulate(rule, &hidden, error, &mut batch.1);
+        self.first.accumulate(rule, input, &hidden_error, &mut batch.0)
+    }
+
+    fn apply(&mut self, rule: &R, batch: (A::Batch, B::Batch), count: usize) {
+        self.first.apply(rule, batch.0, count);
+        self.second.apply(rule, batch.1, count);
+    }
+
+    fn regularization_penalty(&self, rule: &R) -> F {
+        self.first.regularization_penalty(rule) + self.second.regularization_penalty(rule)
+    }
+}
+
+impl<A: Mode, B: Mode> Mode for Chain<A, B> {
+    fn set_train(&mut self, train: bool) {
+        self.first.set_train(train);
+        self.second.set_train(train);
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use Compute;
+    use activations::sigmoid;
+    use feedforward::FeedforwardLayer;
+
+    use super::Chain;
+
+    type Layer = FeedforwardLayer<f32, fn(f32) -> f32, fn(f32) -> f32>;
+
+    #[test]
+    fn serializes_as_json_and_round_trips() {
+        let chain = Chain::new(
+            FeedforwardLayer::new_from(4, 3, sigmoid(), {
+                let mut acc = 0;
+                move || { acc += 1; (1.0f32 + ((13*acc) % 12) as f32) / 13.0f32 }
+            }),
+            FeedforwardLayer::new_from(3, 2, sigmoid(), {
+                let mut acc = 0;
+                move || { acc += 1; (1.0f32 + ((7*acc) % 10) as f32) / 10.0f32 }
+            }),
+        );
+
+        let json = ::serde_json::to_string(&chain).unwrap();
+        let loaded: Chain<Layer, Layer> = ::serde_json::from_str(&json).unwrap();
+
+        assert_eq!(chain.compute(&[1.0, 1.0, 1.0, 1.0]), loaded.compute(&[1.0, 1.0, 1.0, 1.0]));
+    }
+}